@@ -1,15 +1,23 @@
 use std::collections::BTreeMap;
 use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
 
 use im::{Vector, hashmap};
 use modular_agent_core::{
     Agent, AgentContext, AgentData, AgentError, AgentOutput, AgentSpec, AgentValue, AsAgent,
     ModularAgent, async_trait, modular_agent,
 };
-use sqlx::any::{AnyArguments, AnyRow, AnyValueRef, install_default_drivers};
-use sqlx::{Any, AnyPool, Arguments, Column, Decode, Row, TypeInfo, ValueRef};
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use sqlx::any::{AnyArguments, AnyPoolOptions, AnyRow, AnyValueRef, install_default_drivers};
+use sqlx::types::Json;
+use sqlx::types::Uuid;
+use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sqlx::{Any, AnyPool, Arguments, Column, Decode, Executor, Row, TypeInfo, ValueRef};
 
-static DB_MAP: OnceLock<Mutex<BTreeMap<String, AnyPool>>> = OnceLock::new();
+use crate::display::cozo_cell_to_text;
+
+static DB_MAP: OnceLock<Mutex<BTreeMap<(String, DbOptions), AnyPool>>> = OnceLock::new();
 static DRIVERS_INSTALLED: OnceLock<()> = OnceLock::new();
 
 static CATEGORY: &str = "DB/SQLx";
@@ -17,17 +25,59 @@ static CATEGORY: &str = "DB/SQLx";
 static PORT_ARRAY: &str = "array";
 static PORT_VALUE: &str = "value";
 static PORT_TABLE: &str = "table";
+static PORT_ERROR: &str = "error";
 
 static CONFIG_DB: &str = "db";
+static CONFIG_DB_OPTIONS: &str = "db_options";
 static CONFIG_SCRIPT: &str = "script";
+static CONFIG_TRANSACTION: &str = "transaction";
+static CONFIG_STREAM: &str = "stream";
+static CONFIG_BATCH_SIZE: &str = "batch_size";
+
+/// Pool and SQLite connection options, parsed from the `db_options` JSON config.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+struct DbOptions {
+    max_connections: Option<u32>,
+    acquire_timeout_ms: Option<u64>,
+    idle_timeout_ms: Option<u64>,
+    max_lifetime_ms: Option<u64>,
+    /// Issue `PRAGMA foreign_keys=ON` after connecting (SQLite only).
+    foreign_keys: Option<bool>,
+    /// Issue `PRAGMA busy_timeout=<ms>` after connecting (SQLite only).
+    busy_timeout_ms: Option<u64>,
+    /// Issue `PRAGMA journal_mode=WAL` after connecting (SQLite only).
+    wal: Option<bool>,
+}
+
+impl DbOptions {
+    fn from_config_str(raw: &str) -> Result<Self, AgentError> {
+        if raw.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(raw)
+            .map_err(|e| AgentError::InvalidValue(format!("Invalid db_options: {}", e)))
+    }
+}
 
+/// `error` is diagnostic-only: on a database error its structured SQLSTATE
+/// details are always emitted there, best-effort, regardless of whether
+/// anything is wired up to receive them, and the agent still fails with the
+/// same `AgentError` it always has. There's no reliable way to ask the
+/// framework whether an output port is actually connected from here, so
+/// `error` can't be used to suppress that failure for graphs that want to
+/// keep running past a DB error — wire it up to inspect *why* a run failed,
+/// not to keep the graph alive past one.
 #[modular_agent(
     title = "SQLx Script",
     category = CATEGORY,
     inputs = [PORT_VALUE],
-    outputs = [PORT_TABLE],
+    outputs = [PORT_TABLE, PORT_ERROR],
     string_config(name = CONFIG_DB),
-    text_config(name = CONFIG_SCRIPT)
+    text_config(name = CONFIG_DB_OPTIONS),
+    text_config(name = CONFIG_SCRIPT),
+    bool_config(name = CONFIG_TRANSACTION, default = false),
+    bool_config(name = CONFIG_STREAM, default = false),
+    integer_config(name = CONFIG_BATCH_SIZE, default = 100)
 )]
 struct SqlxScriptAgent {
     data: AgentData,
@@ -52,33 +102,254 @@ impl AsAgent for SqlxScriptAgent {
         if script.is_empty() {
             return Ok(());
         }
-        let pool = get_pool(&config.get_string_or_default(CONFIG_DB)).await?;
+        let db = config.get_string_or_default(CONFIG_DB);
+        let db_options = DbOptions::from_config_str(&config.get_string_or_default(CONFIG_DB_OPTIONS))?;
+        let pool = get_pool(&db, &db_options).await?;
+        let dialect = sql_dialect(&normalize_db_url(&db));
+        let transaction = config.get_bool_or_default(CONFIG_TRANSACTION);
+        let stream = config.get_bool_or_default(CONFIG_STREAM);
+
+        if stream && !transaction {
+            let (rewritten, params) = prepare_statement(&script, &value, dialect)?;
+            if script_returns_rows(&rewritten) {
+                let batch_size = config.get_integer_or_default(CONFIG_BATCH_SIZE).max(1) as usize;
+                return self
+                    .stream_table(ctx, &pool, &rewritten, params, batch_size)
+                    .await;
+            }
+        }
+
+        match run_script(&pool, &script, &value, dialect, transaction).await {
+            Ok(value) => self.output(ctx, PORT_TABLE, value).await,
+            Err(SqlxScriptError::Database(db_err)) => {
+                // `error` is diagnostic-only (see the struct doc comment):
+                // always best-effort-emit the structured details, and always
+                // still fail, since `output()`'s return value isn't a
+                // reliable signal of whether the port is actually connected.
+                let error_value = database_error_to_agent_value(db_err.as_ref());
+                let _ = self.output(ctx, PORT_ERROR, error_value).await;
+                Err(AgentError::IoError(format!(
+                    "SQLx Error: {}",
+                    sqlx::Error::Database(db_err)
+                )))
+            }
+            Err(SqlxScriptError::Agent(err)) => Err(err),
+        }
+    }
+}
+
+impl SqlxScriptAgent {
+    /// Stream a SELECT-like query's rows to `PORT_TABLE` in `batch_size`
+    /// chunks instead of buffering the whole result set. The `headers`
+    /// object is sent once, up front, followed by successive `rows` chunks;
+    /// every chunk but the last carries `done: false`, and the last one
+    /// (always emitted, even for a zero-row result or a row count that's an
+    /// exact multiple of `batch_size`) carries `done: true` so accumulating
+    /// consumers like [`TableAccumulator`] know when the table is complete.
+    async fn stream_table(
+        &mut self,
+        ctx: AgentContext,
+        pool: &AnyPool,
+        script: &str,
+        params: AnyArguments<'static>,
+        batch_size: usize,
+    ) -> Result<(), AgentError> {
+        let mut rows_stream = sqlx::query_with(script, params).fetch(pool);
+        let mut batch: Vec<AnyRow> = Vec::with_capacity(batch_size);
+        let mut headers_sent = false;
+
+        loop {
+            let row = match rows_stream.try_next().await {
+                Ok(row) => row,
+                Err(sqlx::Error::Database(db_err)) => {
+                    let error_value = database_error_to_agent_value(db_err.as_ref());
+                    let _ = self.output(ctx.clone(), PORT_ERROR, error_value).await;
+                    return Err(AgentError::IoError(format!(
+                        "SQLx Error: {}",
+                        sqlx::Error::Database(db_err)
+                    )));
+                }
+                Err(e) => return Err(AgentError::IoError(format!("SQLx Error: {}", e))),
+            };
+            let Some(row) = row else { break };
+
+            if !headers_sent {
+                let headers: Vec<String> = row
+                    .columns()
+                    .iter()
+                    .map(|c| c.name().to_string())
+                    .collect();
+                let headers_value =
+                    AgentValue::array(headers.into_iter().map(AgentValue::string).collect());
+                self.output(
+                    ctx.clone(),
+                    PORT_TABLE,
+                    AgentValue::object(hashmap! {
+                        "headers".into() => headers_value,
+                        "done".into() => AgentValue::boolean(false),
+                    }),
+                )
+                .await?;
+                headers_sent = true;
+            }
+
+            batch.push(row);
+            if batch.len() >= batch_size {
+                self.emit_row_batch(&ctx, &mut batch, false).await?;
+            }
+        }
+
+        if headers_sent {
+            // Always emit a final, explicitly-done batch, even if it's empty
+            // because the last batch was already flushed inside the loop
+            // (e.g. the row count was an exact multiple of `batch_size`).
+            self.emit_row_batch(&ctx, &mut batch, true).await?;
+        } else {
+            // Zero-row result: still emit one empty headers+rows object, same
+            // as the buffered path does for an empty result set, instead of
+            // leaving downstream agents with nothing at all.
+            self.output(
+                ctx,
+                PORT_TABLE,
+                AgentValue::object(hashmap! {
+                    "headers".into() => AgentValue::array(Vector::new()),
+                    "rows".into() => AgentValue::array(Vector::new()),
+                    "done".into() => AgentValue::boolean(true),
+                }),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn emit_row_batch(
+        &mut self,
+        ctx: &AgentContext,
+        batch: &mut Vec<AnyRow>,
+        done: bool,
+    ) -> Result<(), AgentError> {
+        let mut row_values: Vector<AgentValue> = Vector::new();
+        for row in batch.iter() {
+            row_values.push_back(sqlx_row_to_agent_value(row)?);
+        }
+        batch.clear();
+        self.output(
+            ctx.clone(),
+            PORT_TABLE,
+            AgentValue::object(hashmap! {
+                "rows".into() => AgentValue::array(row_values),
+                "done".into() => AgentValue::boolean(done),
+            }),
+        )
+        .await
+    }
+}
+
+/// Error from running a script: either an ordinary agent-level failure, or a
+/// structured database error that can be routed to the optional `error` port.
+enum SqlxScriptError {
+    Agent(AgentError),
+    Database(Box<dyn sqlx::error::DatabaseError>),
+}
 
-        let params = build_sqlx_params(&value)?;
-        let value = run_sqlx_statement(&pool, &script, params).await?;
+impl From<AgentError> for SqlxScriptError {
+    fn from(e: AgentError) -> Self {
+        SqlxScriptError::Agent(e)
+    }
+}
 
-        self.output(ctx, PORT_TABLE, value).await
+impl From<sqlx::Error> for SqlxScriptError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::Database(db_err) => SqlxScriptError::Database(db_err),
+            other => SqlxScriptError::Agent(AgentError::IoError(format!("SQLx Error: {}", other))),
+        }
     }
 }
 
-async fn get_pool(db: &str) -> Result<AnyPool, AgentError> {
+/// Map common five-character SQLSTATE classes to a stable, human-readable kind
+/// so downstream agents can branch on failure type without string-matching.
+fn sqlstate_kind(code: &str) -> &'static str {
+    match code {
+        "23505" | "23000" => "unique_violation",
+        "23503" => "foreign_key_violation",
+        "23502" => "not_null_violation",
+        "23514" => "check_violation",
+        "40001" => "serialization_failure",
+        _ if code.starts_with("23") => "integrity_constraint_violation",
+        _ => "database_error",
+    }
+}
+
+fn database_error_to_agent_value(db_err: &dyn sqlx::error::DatabaseError) -> AgentValue {
+    let sqlstate = db_err.code().map(|c| c.into_owned());
+    let kind = sqlstate.as_deref().map(sqlstate_kind).unwrap_or("database_error");
+    AgentValue::object(hashmap! {
+        "sqlstate".into() => sqlstate.map(AgentValue::string).unwrap_or_else(AgentValue::unit),
+        "message".into() => AgentValue::string(db_err.message().to_string()),
+        "constraint".into() => db_err
+            .constraint()
+            .map(|c| AgentValue::string(c.to_string()))
+            .unwrap_or_else(AgentValue::unit),
+        "kind".into() => AgentValue::string(kind.to_string()),
+    })
+}
+
+async fn get_pool(db: &str, options: &DbOptions) -> Result<AnyPool, AgentError> {
     // Install database drivers on first use
     DRIVERS_INSTALLED.get_or_init(install_default_drivers);
 
+    let key = (db.to_string(), options.clone());
     let db_map = DB_MAP.get_or_init(|| Mutex::new(BTreeMap::new()));
-    if let Some(pool) = db_map.lock().unwrap().get(db).cloned() {
+    if let Some(pool) = db_map.lock().unwrap().get(&key).cloned() {
         return Ok(pool);
     }
 
     let url = normalize_db_url(db);
-    let pool = AnyPool::connect(&url)
+    let mut pool_options = AnyPoolOptions::new();
+    if let Some(max_connections) = options.max_connections {
+        pool_options = pool_options.max_connections(max_connections);
+    }
+    if let Some(ms) = options.acquire_timeout_ms {
+        pool_options = pool_options.acquire_timeout(Duration::from_millis(ms));
+    }
+    if let Some(ms) = options.idle_timeout_ms {
+        pool_options = pool_options.idle_timeout(Some(Duration::from_millis(ms)));
+    }
+    if let Some(ms) = options.max_lifetime_ms {
+        pool_options = pool_options.max_lifetime(Some(Duration::from_millis(ms)));
+    }
+
+    if url.starts_with("sqlite:")
+        && (options.foreign_keys.is_some() || options.busy_timeout_ms.is_some() || options.wal.is_some())
+    {
+        let pragmas = options.clone();
+        pool_options = pool_options.after_connect(move |conn, _meta| {
+            let pragmas = pragmas.clone();
+            Box::pin(async move {
+                if pragmas.foreign_keys == Some(true) {
+                    conn.execute("PRAGMA foreign_keys=ON").await?;
+                }
+                if let Some(ms) = pragmas.busy_timeout_ms {
+                    conn.execute(format!("PRAGMA busy_timeout={}", ms).as_str())
+                        .await?;
+                }
+                if pragmas.wal == Some(true) {
+                    conn.execute("PRAGMA journal_mode=WAL").await?;
+                }
+                Ok(())
+            })
+        });
+    }
+
+    let pool = pool_options
+        .connect(&url)
         .await
         .map_err(|e| AgentError::IoError(format!("SQLx Error creating pool: {}", e)))?;
 
     let mut map_guard = db_map.lock().unwrap();
-    let entry = map_guard
-        .entry(db.to_string())
-        .or_insert_with(|| pool.clone());
+    let entry = map_guard.entry(key).or_insert_with(|| pool.clone());
     Ok(entry.clone())
 }
 
@@ -120,6 +391,196 @@ fn normalize_db_url(db: &str) -> String {
     format!("sqlite:{}?mode=rwc", db)
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SqlDialect {
+    Sqlite,
+    MySql,
+    Postgres,
+}
+
+fn sql_dialect(url: &str) -> SqlDialect {
+    if url.starts_with("postgres:") || url.starts_with("postgresql:") {
+        SqlDialect::Postgres
+    } else if url.starts_with("mysql:") {
+        SqlDialect::MySql
+    } else {
+        SqlDialect::Sqlite
+    }
+}
+
+/// One piece of a tokenized SQL script, as produced by `scan_sql_tokens`.
+enum SqlToken {
+    /// A line comment, block comment, or string literal, to be copied through
+    /// unchanged by callers that rewrite or split the surrounding code.
+    Verbatim(String),
+    /// A single character of actual SQL code, outside any comment or string.
+    Code(char),
+}
+
+/// Walk `script`, yielding `Verbatim` runs for `-- ...` / `/* ... */` comments
+/// and `'...'` string literals (respecting `''` escapes), and `Code` for
+/// everything else, one character at a time. This is the shared string-literal
+/// and comment-skipping logic behind `rewrite_named_placeholders` and
+/// `split_sql_statements`, so the two don't drift against each other.
+fn scan_sql_tokens(script: &str) -> Vec<SqlToken> {
+    let chars: Vec<char> = script.chars().collect();
+    let mut tokens = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Line comment: copy through end of line untouched.
+        if c == '-' && chars.get(i + 1) == Some(&'-') {
+            let mut s = String::new();
+            while i < chars.len() && chars[i] != '\n' {
+                s.push(chars[i]);
+                i += 1;
+            }
+            tokens.push(SqlToken::Verbatim(s));
+            continue;
+        }
+
+        // Block comment: copy through closing `*/` untouched.
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let mut s = String::new();
+            s.push(chars[i]);
+            s.push(chars[i + 1]);
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                s.push(chars[i]);
+                i += 1;
+            }
+            if i < chars.len() {
+                s.push(chars[i]);
+                s.push(chars[i + 1]);
+                i += 2;
+            }
+            tokens.push(SqlToken::Verbatim(s));
+            continue;
+        }
+
+        // String literal: copy through the closing quote untouched, respecting `''` escapes.
+        if c == '\'' {
+            let mut s = String::new();
+            s.push(c);
+            i += 1;
+            while i < chars.len() {
+                s.push(chars[i]);
+                if chars[i] == '\'' {
+                    i += 1;
+                    if chars.get(i) == Some(&'\'') {
+                        s.push(chars[i]);
+                        i += 1;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            tokens.push(SqlToken::Verbatim(s));
+            continue;
+        }
+
+        tokens.push(SqlToken::Code(c));
+        i += 1;
+    }
+
+    tokens
+}
+
+/// Rewrite `:name` / `@name` placeholders in `script` into the dialect's native
+/// positional placeholder (`?` for SQLite/MySQL, `$1..$n` for Postgres), using
+/// the string-literal and comment awareness of `scan_sql_tokens` so tokens
+/// inside `'...'`, `-- ...`, and `/* ... */` are left untouched. Returns the
+/// rewritten script along with the parameter names in the order their bound
+/// values must appear.
+fn rewrite_named_placeholders(script: &str, dialect: SqlDialect) -> (String, Vec<String>) {
+    let tokens = scan_sql_tokens(script);
+    let mut out = String::with_capacity(script.len());
+    let mut names: Vec<String> = Vec::new();
+    let mut postgres_index: BTreeMap<String, usize> = BTreeMap::new();
+
+    let is_name_char = |tok: Option<&SqlToken>| matches!(tok, Some(SqlToken::Code(n)) if n.is_alphanumeric() || *n == '_');
+    let is_name_start = |tok: Option<&SqlToken>| matches!(tok, Some(SqlToken::Code(n)) if n.is_alphabetic() || *n == '_');
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            SqlToken::Verbatim(s) => {
+                out.push_str(s);
+                i += 1;
+            }
+            SqlToken::Code(c) => {
+                let c = *c;
+
+                // Postgres `::` cast operator: copy both colons through
+                // untouched so e.g. `data::jsonb` isn't mistaken for a
+                // `:jsonb` placeholder.
+                if c == ':' && matches!(tokens.get(i + 1), Some(SqlToken::Code(':'))) {
+                    out.push(':');
+                    out.push(':');
+                    i += 2;
+                    continue;
+                }
+
+                // Named placeholder.
+                if (c == ':' || c == '@') && is_name_start(tokens.get(i + 1)) {
+                    let mut j = i + 1;
+                    let mut name = String::new();
+                    while is_name_char(tokens.get(j)) {
+                        if let Some(SqlToken::Code(n)) = tokens.get(j) {
+                            name.push(*n);
+                        }
+                        j += 1;
+                    }
+                    match dialect {
+                        SqlDialect::Postgres => {
+                            let next_index = names.len() + 1;
+                            let idx = *postgres_index.entry(name.clone()).or_insert_with(|| {
+                                names.push(name.clone());
+                                next_index
+                            });
+                            out.push_str(&format!("${}", idx));
+                        }
+                        SqlDialect::Sqlite | SqlDialect::MySql => {
+                            names.push(name);
+                            out.push('?');
+                        }
+                    }
+                    i = j;
+                    continue;
+                }
+
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    (out, names)
+}
+
+fn build_named_sqlx_params(
+    value: &AgentValue,
+    names: &[String],
+) -> Result<AnyArguments<'static>, AgentError> {
+    let AgentValue::Object(fields) = value else {
+        return Err(AgentError::InvalidValue(
+            "Named placeholders require an object input".to_string(),
+        ));
+    };
+
+    let mut args = AnyArguments::default();
+    for name in names {
+        let field = fields.get(name.as_str()).ok_or_else(|| {
+            AgentError::InvalidValue(format!("Missing named parameter '{}'", name))
+        })?;
+        add_agent_value_param(&mut args, field)?;
+    }
+    Ok(args)
+}
+
 fn build_sqlx_params(value: &AgentValue) -> Result<AnyArguments<'static>, AgentError> {
     let mut args = AnyArguments::default();
 
@@ -162,17 +623,137 @@ fn add_agent_value_param(
     bind_result.map_err(|e| AgentError::IoError(format!("SQLx Error binding param: {}", e)))
 }
 
+/// Rewrite `statement`'s placeholders against `value` (named if `value` is an
+/// object AND the script actually contains `:name`/`@name` placeholders,
+/// positional otherwise) and build the matching bind arguments. An object
+/// with no named placeholders in the script falls back to the same
+/// positional/JSON-param binding as any other non-array value, so a script
+/// with one plain `?`/`$1` meant to receive a JSON/JSONB payload still binds
+/// correctly.
+fn prepare_statement(
+    statement: &str,
+    value: &AgentValue,
+    dialect: SqlDialect,
+) -> Result<(String, AnyArguments<'static>), AgentError> {
+    if matches!(value, AgentValue::Object(_)) {
+        let (rewritten, names) = rewrite_named_placeholders(statement, dialect);
+        if !names.is_empty() {
+            let params = build_named_sqlx_params(value, &names)?;
+            return Ok((rewritten, params));
+        }
+    }
+
+    let params = build_sqlx_params(value)?;
+    Ok((statement.to_string(), params))
+}
+
+/// Run `script` against `pool`, either as a single statement or, when
+/// `transaction` is set, as multiple `;`-separated statements committed
+/// atomically inside `pool.begin()`.
+async fn run_script(
+    pool: &AnyPool,
+    script: &str,
+    value: &AgentValue,
+    dialect: SqlDialect,
+    transaction: bool,
+) -> Result<AgentValue, SqlxScriptError> {
+    if transaction {
+        let statements: Vec<String> = split_sql_statements(script)
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        run_sqlx_transaction(pool, &statements, value, dialect).await
+    } else {
+        let (rewritten, params) = prepare_statement(script, value, dialect)?;
+        run_sqlx_statement(pool, &rewritten, params).await
+    }
+}
+
+/// Split `script` into individual statements on top-level `;`, using the
+/// string-literal and comment awareness of `scan_sql_tokens` so semicolons
+/// inside `'...'`, `-- ...`, and `/* ... */` don't split a statement.
+fn split_sql_statements(script: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+
+    for token in scan_sql_tokens(script) {
+        match token {
+            SqlToken::Verbatim(s) => current.push_str(&s),
+            SqlToken::Code(';') => statements.push(std::mem::take(&mut current)),
+            SqlToken::Code(c) => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        statements.push(current);
+    }
+    statements
+}
+
+/// Run each of `statements` sequentially inside a single transaction,
+/// committing on success and rolling back (implicitly, on drop) if any
+/// statement fails. Returns the last statement's result set if it returns
+/// rows, otherwise the combined `rows_affected` across all statements.
+async fn run_sqlx_transaction(
+    pool: &AnyPool,
+    statements: &[String],
+    value: &AgentValue,
+    dialect: SqlDialect,
+) -> Result<AgentValue, SqlxScriptError> {
+    let mut tx = pool.begin().await?;
+    let mut total_rows_affected: u64 = 0;
+    let mut last_rows: Option<AgentValue> = None;
+
+    for statement in statements {
+        let (rewritten, params) = prepare_statement(statement, value, dialect)?;
+
+        if script_returns_rows(&rewritten) {
+            let rows: Vec<AnyRow> = sqlx::query_with(rewritten.as_str(), params)
+                .fetch_all(&mut *tx)
+                .await?;
+
+            let headers: Vec<String> = if let Some(first_row) = rows.first() {
+                first_row
+                    .columns()
+                    .iter()
+                    .map(|c| c.name().to_string())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            let headers_value =
+                AgentValue::array(headers.into_iter().map(AgentValue::string).collect());
+            let mut row_values: Vector<AgentValue> = Vector::new();
+            for row in &rows {
+                row_values.push_back(sqlx_row_to_agent_value(row)?);
+            }
+
+            last_rows = Some(AgentValue::object(hashmap! {
+                "headers".into() => headers_value,
+                "rows".into() => AgentValue::array(row_values),
+            }));
+        } else {
+            let result = sqlx::query_with(rewritten.as_str(), params)
+                .execute(&mut *tx)
+                .await?;
+            total_rows_affected += result.rows_affected();
+            last_rows = None;
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(last_rows.unwrap_or_else(|| rows_affected_to_table(total_rows_affected)))
+}
+
 async fn run_sqlx_statement(
     pool: &AnyPool,
     script: &str,
     params: AnyArguments<'static>,
-) -> Result<AgentValue, AgentError> {
+) -> Result<AgentValue, SqlxScriptError> {
     if script_returns_rows(script) {
         // Use fetch_all for SELECT-like queries
-        let rows: Vec<AnyRow> = sqlx::query_with(script, params)
-            .fetch_all(pool)
-            .await
-            .map_err(|e| AgentError::IoError(format!("SQLx Error: {}", e)))?;
+        let rows: Vec<AnyRow> = sqlx::query_with(script, params).fetch_all(pool).await?;
 
         let headers: Vec<String> = if let Some(first_row) = rows.first() {
             first_row
@@ -197,10 +778,7 @@ async fn run_sqlx_statement(
         }))
     } else {
         // Use execute for INSERT/UPDATE/DELETE
-        let result = sqlx::query_with(script, params)
-            .execute(pool)
-            .await
-            .map_err(|e| AgentError::IoError(format!("SQLx Error: {}", e)))?;
+        let result = sqlx::query_with(script, params).execute(pool).await?;
 
         Ok(rows_affected_to_table(result.rows_affected()))
     }
@@ -318,6 +896,55 @@ fn sqlx_value_ref_to_agent_value(value: AnyValueRef<'_>) -> AgentValue {
                 AgentValue::string(type_name)
             }
         }
+        // Date/time types: decode into ISO-8601 strings
+        "DATE" => {
+            if let Ok(v) = <NaiveDate as Decode<Any>>::decode(value) {
+                AgentValue::string(v.format("%Y-%m-%d").to_string())
+            } else {
+                AgentValue::string(type_name)
+            }
+        }
+        "TIME" => {
+            if let Ok(v) = <NaiveTime as Decode<Any>>::decode(value) {
+                AgentValue::string(v.format("%H:%M:%S%.f").to_string())
+            } else {
+                AgentValue::string(type_name)
+            }
+        }
+        "TIMESTAMPTZ" => {
+            if let Ok(v) = <DateTime<Utc> as Decode<Any>>::decode(value) {
+                AgentValue::string(v.to_rfc3339())
+            } else {
+                AgentValue::string(type_name)
+            }
+        }
+        "TIMESTAMP" | "DATETIME" => {
+            if let Ok(v) = <NaiveDateTime as Decode<Any>>::decode(value) {
+                AgentValue::string(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+            } else {
+                AgentValue::string(type_name)
+            }
+        }
+        // UUID: canonical hyphenated form
+        "UUID" => {
+            if let Ok(v) = <Uuid as Decode<Any>>::decode(value) {
+                AgentValue::string(v.to_string())
+            } else {
+                AgentValue::string(type_name)
+            }
+        }
+        // JSON/JSONB: parse into a nested AgentValue via the serde_json bridge
+        "JSON" | "JSONB" => {
+            // Decode through sqlx's typed `Json<T>` wrapper rather than a plain
+            // `String`: Postgres's jsonb wire format carries a leading version
+            // byte that `Json<T>`'s decode strips but a raw string decode would
+            // include verbatim, mangling every jsonb column.
+            if let Ok(Json(json)) = <Json<serde_json::Value> as Decode<Any>>::decode(value) {
+                AgentValue::from_json(json)
+            } else {
+                AgentValue::string(type_name)
+            }
+        }
         _ => {
             // Fallback: try to decode as string
             if let Ok(v) = <String as Decode<Any>>::decode(value) {
@@ -329,6 +956,60 @@ fn sqlx_value_ref_to_agent_value(value: AnyValueRef<'_>) -> AgentValue {
     }
 }
 
+/// Reassembles a `PORT_TABLE` value into a complete `{headers, rows}` table,
+/// whether it arrives as one buffered/transaction message carrying both
+/// fields together and no `done` field, or as `SqlxScriptAgent`'s streaming
+/// mode's split `{headers}` / `{rows}` chunks terminated by a chunk with
+/// `done: true`. A `headers`-only chunk starts a fresh accumulation; a
+/// `rows`-only chunk appends to it; a message carrying both replaces the
+/// accumulated state outright (a new, complete table).
+///
+/// `merge` only returns the accumulated table once `done` is reached (a
+/// message with no `done` field is treated as complete, matching every
+/// buffered-mode table built before streaming existed); it returns `None`
+/// for intermediate streaming chunks so callers that need the finished
+/// result — rather than a growing partial one — act exactly once per
+/// stream instead of re-emitting an ever-larger copy on every batch.
+#[derive(Default)]
+pub(crate) struct TableAccumulator {
+    headers: Vector<AgentValue>,
+    rows: Vector<AgentValue>,
+}
+
+impl TableAccumulator {
+    pub(crate) fn merge(&mut self, value: &AgentValue) -> Option<AgentValue> {
+        let headers = value.get_array("headers");
+
+        if let Some(h) = headers {
+            self.headers = h.clone();
+        }
+        match value.get_array("rows") {
+            Some(r) if headers.is_some() => self.rows = r.clone(),
+            Some(r) => self.rows.extend(r.iter().cloned()),
+            None if headers.is_some() => self.rows.clear(),
+            None => {}
+        }
+
+        let done_field = match value {
+            AgentValue::Object(fields) => fields.get("done"),
+            _ => None,
+        };
+        if matches!(done_field, Some(AgentValue::Boolean(false))) {
+            return None;
+        }
+
+        Some(AgentValue::object(hashmap! {
+            "headers".into() => AgentValue::array(self.headers.clone()),
+            "rows".into() => AgentValue::array(self.rows.clone()),
+        }))
+    }
+}
+
+/// Emits the full `rows` array once the incoming table is complete. When fed
+/// by `SqlxScriptAgent`'s streaming mode, intermediate chunks are buffered
+/// silently and nothing is emitted until the final chunk arrives, so a
+/// multi-batch stream still produces exactly one output instead of an
+/// ever-growing copy on every batch.
 #[modular_agent(
     title = "Rows",
     category = CATEGORY,
@@ -337,6 +1018,7 @@ fn sqlx_value_ref_to_agent_value(value: AnyValueRef<'_>) -> AgentValue {
 )]
 struct RowsAgent {
     data: AgentData,
+    accumulator: TableAccumulator,
 }
 
 #[async_trait]
@@ -344,6 +1026,7 @@ impl AsAgent for RowsAgent {
     fn new(ma: ModularAgent, id: String, spec: AgentSpec) -> Result<Self, AgentError> {
         Ok(Self {
             data: AgentData::new(ma, id, spec),
+            accumulator: TableAccumulator::default(),
         })
     }
 
@@ -353,7 +1036,10 @@ impl AsAgent for RowsAgent {
         _port: String,
         value: AgentValue,
     ) -> Result<(), AgentError> {
-        let rows = value
+        let Some(table) = self.accumulator.merge(&value) else {
+            return Ok(());
+        };
+        let rows = table
             .get_array("rows")
             .ok_or_else(|| AgentError::InvalidValue("Missing 'rows' field".to_string()))?;
         self.output(ctx, PORT_ARRAY, AgentValue::array(rows.clone()))
@@ -361,6 +1047,8 @@ impl AsAgent for RowsAgent {
     }
 }
 
+/// Emits the row at `index` once the incoming table is complete; see
+/// [`RowsAgent`] for how this behaves against a streamed table.
 #[modular_agent(
     title = "Row",
     category = CATEGORY,
@@ -370,6 +1058,7 @@ impl AsAgent for RowsAgent {
 )]
 struct RowAgent {
     data: AgentData,
+    accumulator: TableAccumulator,
 }
 
 #[async_trait]
@@ -377,6 +1066,7 @@ impl AsAgent for RowAgent {
     fn new(ma: ModularAgent, id: String, spec: AgentSpec) -> Result<Self, AgentError> {
         Ok(Self {
             data: AgentData::new(ma, id, spec),
+            accumulator: TableAccumulator::default(),
         })
     }
 
@@ -387,17 +1077,23 @@ impl AsAgent for RowAgent {
         value: AgentValue,
     ) -> Result<(), AgentError> {
         let index = self.configs()?.get_integer("index")? as usize;
-        let row = value
+        let Some(table) = self.accumulator.merge(&value) else {
+            return Ok(());
+        };
+        let row = table
             .get_array("rows")
             .ok_or_else(|| AgentError::InvalidValue("Missing 'rows' field".to_string()))?
             .get(index)
             .ok_or_else(|| {
                 AgentError::InvalidValue(format!("Row index {} out of bounds", index))
-            })?;
-        self.output(ctx, PORT_ARRAY, row.clone()).await
+            })?
+            .clone();
+        self.output(ctx, PORT_ARRAY, row).await
     }
 }
 
+/// Projects `cols` out of the incoming table once it's complete; see
+/// [`RowsAgent`] for how this behaves against a streamed table.
 #[modular_agent(
     title = "Select",
     category = CATEGORY,
@@ -407,6 +1103,7 @@ impl AsAgent for RowAgent {
 )]
 struct SelectAgent {
     data: AgentData,
+    accumulator: TableAccumulator,
 }
 
 #[async_trait]
@@ -414,6 +1111,7 @@ impl AsAgent for SelectAgent {
     fn new(ma: ModularAgent, id: String, spec: AgentSpec) -> Result<Self, AgentError> {
         Ok(Self {
             data: AgentData::new(ma, id, spec),
+            accumulator: TableAccumulator::default(),
         })
     }
 
@@ -429,6 +1127,9 @@ impl AsAgent for SelectAgent {
             .split(',')
             .map(|s| s.trim().to_string())
             .collect::<Vec<String>>();
+        let Some(value) = self.accumulator.merge(&value) else {
+            return Ok(());
+        };
         let headers = value
             .get_array("headers")
             .ok_or_else(|| AgentError::InvalidValue("Missing 'headers' field".to_string()))?;
@@ -470,3 +1171,134 @@ impl AsAgent for SelectAgent {
         }
     }
 }
+
+static PORT_RESULT: &str = "result";
+
+static CONFIG_EXPECTED: &str = "expected";
+static CONFIG_SORT_MODE: &str = "sort_mode";
+static CONFIG_HASH_THRESHOLD: &str = "hash_threshold";
+
+/// sqllogictest-style comparison mode for [`AssertTableAgent`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SortMode {
+    NoSort,
+    RowSort,
+    ValueSort,
+}
+
+impl SortMode {
+    fn parse(raw: &str) -> Result<Self, AgentError> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "nosort" => Ok(SortMode::NoSort),
+            "rowsort" => Ok(SortMode::RowSort),
+            "valuesort" => Ok(SortMode::ValueSort),
+            other => Err(AgentError::InvalidValue(format!(
+                "Unknown sort_mode '{}': expected nosort, rowsort, or valuesort",
+                other
+            ))),
+        }
+    }
+}
+
+/// Compares the incoming table against `expected` once it's complete. Fed by
+/// `SqlxScriptAgent`'s streaming mode, this waits for the final chunk before
+/// judging `passed`, rather than asserting against a headers-only or
+/// partially-accumulated table.
+#[modular_agent(
+    title = "Assert Table",
+    category = CATEGORY,
+    inputs = [PORT_TABLE],
+    outputs = [PORT_RESULT],
+    text_config(name = CONFIG_EXPECTED),
+    string_config(name = CONFIG_SORT_MODE, default = "nosort"),
+    integer_config(name = CONFIG_HASH_THRESHOLD, default = 0),
+)]
+struct AssertTableAgent {
+    data: AgentData,
+    accumulator: TableAccumulator,
+}
+
+#[async_trait]
+impl AsAgent for AssertTableAgent {
+    fn new(ma: ModularAgent, id: String, spec: AgentSpec) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AgentData::new(ma, id, spec),
+            accumulator: TableAccumulator::default(),
+        })
+    }
+
+    async fn process(
+        &mut self,
+        ctx: AgentContext,
+        _port: String,
+        value: AgentValue,
+    ) -> Result<(), AgentError> {
+        let config = self.configs()?;
+        let sort_mode = SortMode::parse(&config.get_string_or_default(CONFIG_SORT_MODE))?;
+        let hash_threshold = config.get_integer_or_default(CONFIG_HASH_THRESHOLD).max(0) as usize;
+        let expected_raw = config.get_string(CONFIG_EXPECTED)?;
+        let Some(value) = self.accumulator.merge(&value) else {
+            return Ok(());
+        };
+
+        let actual_rows: Vec<Vec<String>> = value
+            .get_array("rows")
+            .ok_or_else(|| AgentError::InvalidValue("Missing 'rows' field".to_string()))?
+            .iter()
+            .map(|row| {
+                let cells = row
+                    .as_array()
+                    .ok_or_else(|| AgentError::InvalidValue("Row is not an array".to_string()))?;
+                Ok(cells.iter().map(cozo_cell_to_text).collect())
+            })
+            .collect::<Result<Vec<Vec<String>>, AgentError>>()?;
+
+        let expected_rows: Vec<Vec<String>> = expected_raw
+            .lines()
+            .map(|line| line.split('\t').map(str::to_string).collect())
+            .collect();
+
+        let total_values: usize = actual_rows.iter().map(Vec::len).sum();
+
+        let (expected_repr, actual_repr, passed) = if hash_threshold > 0 && total_values > hash_threshold {
+            let actual_values = apply_sort_mode(sort_mode, &actual_rows);
+            let digest = md5::compute(actual_values.join("\n").as_bytes());
+            let actual_repr = format!("{} values hashing to {:x}", total_values, digest);
+            let passed = expected_raw.trim() == actual_repr;
+            (expected_raw.trim().to_string(), actual_repr, passed)
+        } else {
+            let expected_values = apply_sort_mode(sort_mode, &expected_rows);
+            let actual_values = apply_sort_mode(sort_mode, &actual_rows);
+            let passed = expected_values == actual_values;
+            (expected_values.join("\n"), actual_values.join("\n"), passed)
+        };
+
+        let result = AgentValue::object(hashmap! {
+            "passed".into() => AgentValue::boolean(passed),
+            "expected".into() => AgentValue::string(expected_repr),
+            "actual".into() => AgentValue::string(actual_repr),
+        });
+
+        self.output(ctx, PORT_RESULT, result).await
+    }
+}
+
+/// Render rows to comparable strings per the sqllogictest sort mode: `nosort`
+/// keeps each row as a tab-joined line in place, `rowsort` sorts those lines
+/// lexicographically, and `valuesort` flattens every cell across all rows into
+/// one sorted list.
+fn apply_sort_mode(mode: SortMode, rows: &[Vec<String>]) -> Vec<String> {
+    match mode {
+        SortMode::NoSort => rows.iter().map(|row| row.join("\t")).collect(),
+        SortMode::RowSort => {
+            let mut lines: Vec<String> = rows.iter().map(|row| row.join("\t")).collect();
+            lines.sort();
+            lines
+        }
+        SortMode::ValueSort => {
+            let mut values: Vec<String> = rows.iter().flatten().cloned().collect();
+            values.sort();
+            values
+        }
+    }
+}
@@ -3,6 +3,8 @@ use modular_agent_kit::{
     async_trait, modular_agent,
 };
 
+use crate::agents::TableAccumulator;
+
 static CATEGORY: &str = "DB/SQLx";
 
 static PORT_TABLE: &str = "table";
@@ -10,6 +12,9 @@ static PORT_TABLE: &str = "table";
 static CONFIG_TABLE: &str = "table";
 
 // SQLx Display Table
+/// Renders the incoming table to HTML once it's complete; see
+/// [`crate::agents::RowsAgent`] for how this behaves against a streamed
+/// table.
 #[modular_agent(
     kind = "Display",
     title = "Display Table",
@@ -25,6 +30,7 @@ static CONFIG_TABLE: &str = "table";
 )]
 struct DisplayTableAgent {
     data: AgentData,
+    accumulator: TableAccumulator,
 }
 
 #[async_trait]
@@ -32,6 +38,7 @@ impl AsAgent for DisplayTableAgent {
     fn new(mak: MAK, id: String, spec: AgentSpec) -> Result<Self, AgentError> {
         Ok(Self {
             data: AgentData::new(mak, id, spec),
+            accumulator: TableAccumulator::default(),
         })
     }
 
@@ -41,8 +48,11 @@ impl AsAgent for DisplayTableAgent {
         _port: String,
         value: AgentValue,
     ) -> Result<(), AgentError> {
-        let headers = value.get_array("headers");
-        let rows = value.get_array("rows");
+        let Some(table) = self.accumulator.merge(&value) else {
+            return Ok(());
+        };
+        let headers = table.get_array("headers");
+        let rows = table.get_array("rows");
 
         let table_html = generate_html_table(headers, rows);
 
@@ -66,7 +76,7 @@ fn escape_html(text: &str) -> String {
     escaped
 }
 
-fn cozo_cell_to_text(value: &AgentValue) -> String {
+pub(crate) fn cozo_cell_to_text(value: &AgentValue) -> String {
     match value {
         AgentValue::Unit => "null".to_string(),
         AgentValue::Boolean(b) => b.to_string(),